@@ -2,26 +2,57 @@
 //!
 //! A simple HTTP service that distributes testnet tokens for TPS testing.
 
+mod metrics;
+mod store;
+
 use anyhow::{anyhow, Result};
 use axum::{
-    extract::{Json, State},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{Json, Query, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Router,
 };
 use chrono::{DateTime, Utc};
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
+use metrics::Metrics;
 use serde::{Deserialize, Serialize};
-use std::{net::SocketAddr, sync::Arc, time::Duration};
-use tokio::sync::RwLock;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use store::{DripRecord, FaucetStore, InMemoryStore};
+use tokio::sync::{Mutex, RwLock};
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, warn};
+use ulid::Ulid;
+
+// Default faucet configuration. Live values are held in `Config` behind an
+// `Arc<RwLock<_>>` so the admin API can retune them without a restart.
+const DEFAULT_DRIP_AMOUNT: u128 = 10_000_000_000_000; // 10 tokens (with 12 decimals)
+const DEFAULT_RATE_LIMIT_SECONDS: i64 = 60; // 1 minute between requests per address
+const DEFAULT_MAX_PENDING_TXS: usize = 100;
+const DEFAULT_CHALLENGE_REQUIRED: bool = true;
+
+// Pending-tx reaper configuration
+const PENDING_TX_SWEEP_INTERVAL_SECONDS: u64 = 15;
+const PENDING_TX_TTL_SECONDS: i64 = 300; // drop a tx that hasn't been confirmed within 5 minutes
 
-// Faucet configuration
-const DRIP_AMOUNT: u128 = 10_000_000_000_000; // 10 tokens (with 12 decimals)
-const RATE_LIMIT_SECONDS: i64 = 60; // 1 minute between requests per address
-const MAX_PENDING_TXS: usize = 100;
+// Validator health-monitor configuration
+const VALIDATOR_HEALTH_CHECK_INTERVAL_SECONDS: u64 = 10;
+const SUBMIT_MAX_ATTEMPTS: usize = VALIDATORS.len();
+
+// Proof-of-work challenge configuration
+const CHALLENGE_TTL_SECONDS: i64 = 120; // how long an issued challenge stays valid
+const CHALLENGE_SWEEP_INTERVAL_SECONDS: u64 = 30;
+const DEFAULT_CHALLENGE_DIFFICULTY_BITS: u32 = 16; // required leading zero bits in sha256(token || nonce)
 
 // Validator endpoints
 const VALIDATORS: &[&str] = &[
@@ -40,9 +71,77 @@ const ALICE_ADDRESS: &str = "5HDjAbVHMuJzezSccj6eFrEA6nKjonrFRm8h7aTiJXSHP5Qi";
 
 #[derive(Clone)]
 struct AppState {
-    rate_limits: Arc<DashMap<String, DateTime<Utc>>>,
+    store: Arc<dyn FaucetStore>,
     pending_txs: Arc<RwLock<Vec<PendingTx>>>,
+    /// Bumped on every operation that replaces `pending_txs` wholesale (currently
+    /// just `/admin/pending/flush`). Lets the reaper detect that its in-flight
+    /// snapshot went stale and avoid clobbering the replacement.
+    pending_generation: Arc<AtomicU64>,
     active_validator: Arc<RwLock<String>>,
+    alice_nonce: Arc<Mutex<Option<u32>>>,
+    validator_health: Arc<DashMap<String, ValidatorHealth>>,
+    config: Arc<RwLock<Config>>,
+    banned_addresses: Arc<DashSet<String>>,
+    admin_token: Option<Arc<str>>,
+    metrics: Arc<Metrics>,
+    challenges: Arc<DashMap<String, Challenge>>,
+}
+
+/// An issued proof-of-work challenge, keyed by its ULID token in `AppState::challenges`.
+#[derive(Clone, Copy, Debug)]
+struct Challenge {
+    difficulty_bits: u32,
+    issued_at: DateTime<Utc>,
+}
+
+/// Runtime-tunable faucet settings. Read by the public handlers, mutated only
+/// through the `/admin/config` endpoint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Config {
+    drip_amount: u128,
+    rate_limit_seconds: i64,
+    max_pending_txs: usize,
+    /// Whether `/drip` requires a solved proof-of-work challenge. Defaults to
+    /// `true`; an admin can disable it (e.g. for a trusted internal testnet)
+    /// via `/admin/config`.
+    challenge_required: bool,
+    /// Required leading zero bits in sha256(token || nonce); higher costs the
+    /// client more compute per `/drip`.
+    challenge_difficulty_bits: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            drip_amount: DEFAULT_DRIP_AMOUNT,
+            rate_limit_seconds: DEFAULT_RATE_LIMIT_SECONDS,
+            max_pending_txs: DEFAULT_MAX_PENDING_TXS,
+            challenge_required: DEFAULT_CHALLENGE_REQUIRED,
+            challenge_difficulty_bits: DEFAULT_CHALLENGE_DIFFICULTY_BITS,
+        }
+    }
+}
+
+/// Partial update for `Config`; absent fields leave the current value untouched.
+#[derive(Deserialize, Default)]
+struct ConfigUpdate {
+    drip_amount: Option<u128>,
+    rate_limit_seconds: Option<i64>,
+    max_pending_txs: Option<usize>,
+    challenge_required: Option<bool>,
+    challenge_difficulty_bits: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct AddressRequest {
+    address: String,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+struct ValidatorHealth {
+    healthy: bool,
+    latency_ms: Option<u64>,
+    last_checked: DateTime<Utc>,
 }
 
 #[derive(Clone)]
@@ -50,11 +149,35 @@ struct PendingTx {
     to: String,
     amount: u128,
     timestamp: DateTime<Utc>,
+    tx_hash: String,
+    status: TxStatus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TxStatus {
+    Submitted,
+    Confirmed,
+    Expired,
 }
 
 #[derive(Deserialize)]
 struct DripRequest {
     address: String,
+    /// Token returned by `/challenge`; burned on use whether or not it verifies.
+    /// Only required when `Config::challenge_required` is set.
+    #[serde(default)]
+    challenge_token: Option<String>,
+    /// Nonce the client found such that sha256(token || nonce) has enough leading zero bits.
+    #[serde(default)]
+    challenge_nonce: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ChallengeResponse {
+    token: String,
+    difficulty_bits: u32,
+    expires_in_seconds: i64,
 }
 
 #[derive(Serialize)]
@@ -74,6 +197,27 @@ struct StatusResponse {
     rate_limit_seconds: i64,
 }
 
+#[derive(Serialize)]
+struct PendingTxResponse {
+    to: String,
+    amount: String,
+    timestamp: DateTime<Utc>,
+    tx_hash: String,
+    status: TxStatus,
+}
+
+impl From<&PendingTx> for PendingTxResponse {
+    fn from(tx: &PendingTx) -> Self {
+        PendingTxResponse {
+            to: tx.to.clone(),
+            amount: format!("{} QHT", tx.amount / 1_000_000_000_000),
+            timestamp: tx.timestamp,
+            tx_hash: tx.tx_hash.clone(),
+            status: tx.status,
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct HealthResponse {
     healthy: bool,
@@ -136,6 +280,10 @@ async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
         }
     }
 
+    if let Some(height) = block_height {
+        state.metrics.block_height.set(height as i64);
+    }
+
     let response = HealthResponse {
         healthy: validators_online > 0,
         validators_online,
@@ -152,24 +300,206 @@ async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
 async fn status(State(state): State<AppState>) -> impl IntoResponse {
     let validator = state.active_validator.read().await.clone();
     let pending = state.pending_txs.read().await.len();
+    let config = state.config.read().await.clone();
 
     Json(StatusResponse {
         status: "running".to_string(),
         active_validator: validator,
         pending_txs: pending,
-        drip_amount: format!("{} QHT", DRIP_AMOUNT / 1_000_000_000_000),
-        rate_limit_seconds: RATE_LIMIT_SECONDS,
+        drip_amount: format!("{} QHT", config.drip_amount / 1_000_000_000_000),
+        rate_limit_seconds: config.rate_limit_seconds,
+    })
+}
+
+/// Issue a proof-of-work challenge that `/drip` will later require a solution for.
+async fn challenge(State(state): State<AppState>) -> impl IntoResponse {
+    let difficulty_bits = state.config.read().await.challenge_difficulty_bits;
+    let token = Ulid::new().to_string();
+    state.challenges.insert(token.clone(), Challenge { difficulty_bits, issued_at: Utc::now() });
+
+    Json(ChallengeResponse {
+        token,
+        difficulty_bits,
+        expires_in_seconds: CHALLENGE_TTL_SECONDS,
     })
 }
 
+/// True if sha256(token || nonce) has at least `difficulty_bits` leading zero bits.
+fn verify_pow(token: &str, nonce: &str, difficulty_bits: u32) -> bool {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hasher.update(nonce.as_bytes());
+    leading_zero_bits(&hasher.finalize()) >= difficulty_bits
+}
+
+fn leading_zero_bits(bytes: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in bytes {
+        if *byte == 0 {
+            bits += 8;
+            continue;
+        }
+        bits += byte.leading_zeros();
+        break;
+    }
+    bits
+}
+
+/// Periodically drops challenge tokens that were never redeemed before `CHALLENGE_TTL_SECONDS`.
+async fn spawn_challenge_sweeper(state: AppState) {
+    let mut interval = tokio::time::interval(Duration::from_secs(CHALLENGE_SWEEP_INTERVAL_SECONDS));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+
+        let now = Utc::now();
+        let before = state.challenges.len();
+        state.challenges.retain(|_, c| now.signed_duration_since(c.issued_at).num_seconds() <= CHALLENGE_TTL_SECONDS);
+        let removed = before - state.challenges.len();
+
+        if removed > 0 {
+            info!("Challenge sweeper expired {} stale challenge(s)", removed);
+        }
+    }
+}
+
+async fn metrics_endpoint(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
+
+async fn pending(State(state): State<AppState>) -> impl IntoResponse {
+    let pending = state.pending_txs.read().await;
+    let views: Vec<PendingTxResponse> = pending.iter().map(PendingTxResponse::from).collect();
+    Json(views)
+}
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+    address: String,
+}
+
+async fn history(State(state): State<AppState>, Query(query): Query<HistoryQuery>) -> impl IntoResponse {
+    let records = state.store.history_for(query.address.trim()).await;
+    Json(records)
+}
+
+/// Gates every `/admin/*` route behind `Authorization: Bearer <ADMIN_TOKEN>`.
+/// If no token was configured at startup, the admin API is disabled entirely.
+async fn require_admin_token(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let Some(expected) = &state.admin_token else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "admin API disabled: ADMIN_TOKEN not set").into_response();
+    };
+
+    let provided = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    if provided != Some(expected.as_ref()) {
+        return (StatusCode::UNAUTHORIZED, "invalid or missing admin token").into_response();
+    }
+
+    next.run(req).await
+}
+
+async fn admin_get_config(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.config.read().await.clone())
+}
+
+async fn admin_update_config(State(state): State<AppState>, Json(update): Json<ConfigUpdate>) -> impl IntoResponse {
+    let mut config = state.config.write().await;
+
+    if let Some(drip_amount) = update.drip_amount {
+        config.drip_amount = drip_amount;
+    }
+    if let Some(rate_limit_seconds) = update.rate_limit_seconds {
+        config.rate_limit_seconds = rate_limit_seconds;
+    }
+    if let Some(max_pending_txs) = update.max_pending_txs {
+        config.max_pending_txs = max_pending_txs;
+    }
+    if let Some(challenge_required) = update.challenge_required {
+        config.challenge_required = challenge_required;
+    }
+    if let Some(challenge_difficulty_bits) = update.challenge_difficulty_bits {
+        config.challenge_difficulty_bits = challenge_difficulty_bits;
+    }
+
+    info!("Admin updated config: {:?}", *config);
+    Json(config.clone())
+}
+
+async fn admin_ban(State(state): State<AppState>, Json(request): Json<AddressRequest>) -> impl IntoResponse {
+    let address = request.address.trim().to_string();
+    info!("Admin banned address {}", address);
+    state.banned_addresses.insert(address);
+    StatusCode::NO_CONTENT
+}
+
+async fn admin_unban(State(state): State<AppState>, Json(request): Json<AddressRequest>) -> impl IntoResponse {
+    let address = request.address.trim();
+    info!("Admin unbanned address {}", address);
+    state.banned_addresses.remove(address);
+    StatusCode::NO_CONTENT
+}
+
+async fn admin_refresh_validator(State(state): State<AppState>) -> impl IntoResponse {
+    refresh_validator_health(&state).await;
+    let active_validator = state.active_validator.read().await.clone();
+    info!("Admin forced a validator refresh; active validator is now {}", active_validator);
+
+    // Surface the full health snapshot (including when each entry was last
+    // probed) rather than just the winner, so an operator can tell a validator
+    // that's merely slow apart from one this refresh never managed to reach.
+    let validators: HashMap<String, ValidatorHealth> = state
+        .validator_health
+        .iter()
+        .map(|entry| (entry.key().clone(), *entry.value()))
+        .collect();
+
+    Json(serde_json::json!({ "active_validator": active_validator, "validators": validators }))
+}
+
+async fn admin_flush_pending(State(state): State<AppState>) -> impl IntoResponse {
+    let mut pending = state.pending_txs.write().await;
+    let flushed = pending.len();
+    pending.clear();
+    // Bump the generation so a reaper sweep already in flight notices its
+    // snapshot went stale and doesn't resurrect what was just flushed.
+    state.pending_generation.fetch_add(1, Ordering::SeqCst);
+    state.metrics.pending_count.set(0);
+    info!("Admin flushed {} pending tx(es)", flushed);
+    Json(serde_json::json!({ "flushed": flushed }))
+}
+
 async fn drip(
     State(state): State<AppState>,
     Json(request): Json<DripRequest>,
 ) -> impl IntoResponse {
     let address = request.address.trim().to_string();
 
+    // Banned addresses are rejected before any other check
+    if state.banned_addresses.contains(&address) {
+        state.metrics.drips_rejected.with_label_values(&["banned"]).inc();
+        return (
+            StatusCode::FORBIDDEN,
+            Json(DripResponse {
+                success: false,
+                message: "This address is banned from using the faucet".to_string(),
+                tx_hash: None,
+                amount: "0".to_string(),
+            }),
+        );
+    }
+
     // Validate address format (should start with 5 for Substrate)
     if !address.starts_with('5') || address.len() != 48 {
+        state.metrics.drips_rejected.with_label_values(&["bad_address"]).inc();
         return (
             StatusCode::BAD_REQUEST,
             Json(DripResponse {
@@ -181,12 +511,64 @@ async fn drip(
         );
     }
 
+    let config = state.config.read().await.clone();
+
+    // Require a solved, unexpired, unused proof-of-work challenge, unless an
+    // admin has disabled that requirement via `/admin/config`. The token is
+    // burned on removal so a solution can never be replayed.
+    if config.challenge_required {
+        let challenge_token = request.challenge_token.clone().unwrap_or_default();
+        match state.challenges.remove(&challenge_token) {
+            Some((_, challenge)) => {
+                let age = Utc::now().signed_duration_since(challenge.issued_at);
+                if age.num_seconds() > CHALLENGE_TTL_SECONDS {
+                    state.metrics.drips_rejected.with_label_values(&["challenge_expired"]).inc();
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(DripResponse {
+                            success: false,
+                            message: "Challenge expired, request a new one from /challenge".to_string(),
+                            tx_hash: None,
+                            amount: "0".to_string(),
+                        }),
+                    );
+                }
+                let challenge_nonce = request.challenge_nonce.as_deref().unwrap_or("");
+                if !verify_pow(&challenge_token, challenge_nonce, challenge.difficulty_bits) {
+                    state.metrics.drips_rejected.with_label_values(&["challenge_invalid"]).inc();
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(DripResponse {
+                            success: false,
+                            message: "Invalid proof-of-work solution".to_string(),
+                            tx_hash: None,
+                            amount: "0".to_string(),
+                        }),
+                    );
+                }
+            }
+            None => {
+                state.metrics.drips_rejected.with_label_values(&["challenge_missing"]).inc();
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(DripResponse {
+                        success: false,
+                        message: "Missing or already-used challenge token; request one from /challenge".to_string(),
+                        tx_hash: None,
+                        amount: "0".to_string(),
+                    }),
+                );
+            }
+        }
+    }
+
     // Check rate limit
     let now = Utc::now();
-    if let Some(last_request) = state.rate_limits.get(&address) {
-        let elapsed = now.signed_duration_since(*last_request);
-        if elapsed.num_seconds() < RATE_LIMIT_SECONDS {
-            let wait_time = RATE_LIMIT_SECONDS - elapsed.num_seconds();
+    if let Some(last_request) = state.store.last_drip_for(&address).await {
+        let elapsed = now.signed_duration_since(last_request);
+        if elapsed.num_seconds() < config.rate_limit_seconds {
+            let wait_time = config.rate_limit_seconds - elapsed.num_seconds();
+            state.metrics.drips_rejected.with_label_values(&["rate_limit"]).inc();
             return (
                 StatusCode::TOO_MANY_REQUESTS,
                 Json(DripResponse {
@@ -202,7 +584,8 @@ async fn drip(
     // Check pending tx limit
     {
         let pending = state.pending_txs.read().await;
-        if pending.len() >= MAX_PENDING_TXS {
+        if pending.len() >= config.max_pending_txs {
+            state.metrics.drips_rejected.with_label_values(&["pending_cap"]).inc();
             return (
                 StatusCode::SERVICE_UNAVAILABLE,
                 Json(DripResponse {
@@ -217,23 +600,38 @@ async fn drip(
 
     // Submit transaction via RPC
     let validator = state.active_validator.read().await.clone();
+    state.metrics.drips_attempted.inc();
 
-    match submit_transfer(&state, &validator, &address, DRIP_AMOUNT).await {
+    match submit_transfer(&state, &validator, &address, config.drip_amount).await {
         Ok(tx_hash) => {
-            // Update rate limit
-            state.rate_limits.insert(address.clone(), now);
+            state.metrics.drips_succeeded.inc();
+
+            // Update rate limit and durable history
+            state.store.record_drip(&address, now).await;
+            state
+                .store
+                .append_history(DripRecord {
+                    address: address.clone(),
+                    amount: config.drip_amount,
+                    tx_hash: tx_hash.clone(),
+                    timestamp: now,
+                })
+                .await;
 
             // Add to pending txs
             {
                 let mut pending = state.pending_txs.write().await;
                 pending.push(PendingTx {
                     to: address.clone(),
-                    amount: DRIP_AMOUNT,
+                    amount: config.drip_amount,
                     timestamp: now,
+                    tx_hash: tx_hash.clone(),
+                    status: TxStatus::Submitted,
                 });
+                state.metrics.pending_count.set(pending.len() as i64);
             }
 
-            info!("Drip sent to {}: {} (tx: {})", address, DRIP_AMOUNT, tx_hash);
+            info!("Drip sent to {}: {} (tx: {})", address, config.drip_amount, tx_hash);
 
             (
                 StatusCode::OK,
@@ -241,11 +639,12 @@ async fn drip(
                     success: true,
                     message: "Tokens sent successfully!".to_string(),
                     tx_hash: Some(tx_hash),
-                    amount: format!("{} QHT", DRIP_AMOUNT / 1_000_000_000_000),
+                    amount: format!("{} QHT", config.drip_amount / 1_000_000_000_000),
                 }),
             )
         }
         Err(e) => {
+            state.metrics.drips_failed.inc();
             warn!("Failed to send drip to {}: {}", address, e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -317,16 +716,164 @@ async fn get_nonce(validator_url: &str, address: &str) -> Result<u32> {
     Ok(json["result"].as_u64().unwrap_or(0) as u32)
 }
 
-/// Submit transfer using gateway_submit RPC (handles SPHINCS+ signing internally)
-async fn submit_transfer(_state: &AppState, validator_url: &str, to: &str, amount: u128) -> Result<String> {
+/// Claim the next nonce for Alice, seeding the cache from chain on first use.
+/// The fetch-and-increment happens under the lock so concurrent `/drip` calls
+/// never hand out the same nonce.
+async fn allocate_nonce(state: &AppState, validator_url: &str) -> Result<u32> {
+    let mut cached = state.alice_nonce.lock().await;
+
+    let nonce = match *cached {
+        Some(nonce) => nonce,
+        None => get_nonce(validator_url, ALICE_ADDRESS).await?,
+    };
+
+    *cached = Some(nonce + 1);
+    Ok(nonce)
+}
+
+/// Drop the cached nonce so the next `allocate_nonce` call re-seeds from chain.
+/// Used when the gateway rejects a submission as stale or too far in the future.
+async fn invalidate_nonce_cache(state: &AppState) {
+    *state.alice_nonce.lock().await = None;
+}
+
+/// Submit transfer using gateway_submit RPC, failing over to the next healthy
+/// validator if the current one is unreachable rather than surfacing a 500.
+/// Seeding the nonce cache (when it is empty) is not failed over: if that
+/// `get_nonce` call to `validator_url` fails, its error is returned as is.
+async fn submit_transfer(state: &AppState, validator_url: &str, to: &str, amount: u128) -> Result<String> {
+    let mut current = validator_url.to_string();
+    let mut last_err = None;
+
+    // Claim the nonce once per logical submission. A transport error below means
+    // the node never saw this attempt, so every retry against another validator
+    // must reuse the same nonce rather than claiming (and thereby skipping) a new
+    // one; an RPC-level nonce rejection still invalidates the cache separately.
+    let nonce = allocate_nonce(state, &current).await?;
+
+    for _ in 0..SUBMIT_MAX_ATTEMPTS {
+        match submit_transfer_once(state, &current, to, amount, nonce).await {
+            Ok(tx_hash) => return Ok(tx_hash),
+            Err(e) if is_transport_error(&e) => {
+                warn!("Validator {} unreachable ({}), failing over", current, e);
+                mark_validator_unhealthy(state, &current).await;
+                last_err = Some(e);
+
+                match pick_next_healthy_validator(state, &current) {
+                    Some(next) => current = next,
+                    None => break,
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    // Every attempt failed at the transport level, so the nonce claimed above
+    // was never seen by any node. Drop the cache so the next submission re-seeds
+    // from chain instead of permanently skipping it and wedging the faucet.
+    invalidate_nonce_cache(state).await;
+    Err(last_err.unwrap_or_else(|| anyhow!("No healthy validators available")))
+}
+
+/// True when `e` came from the transport (connection refused, timeout, ...) rather
+/// than an RPC-level error returned by a reachable node.
+fn is_transport_error(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<reqwest::Error>().map_or(false, |re| !re.is_decode())
+}
+
+async fn mark_validator_unhealthy(state: &AppState, validator_url: &str) {
+    state.validator_health.insert(
+        validator_url.to_string(),
+        ValidatorHealth { healthy: false, latency_ms: None, last_checked: Utc::now() },
+    );
+}
+
+/// Pick the lowest-latency healthy validator other than `exclude`. Falls back to
+/// the next configured endpoint if the health map has no data yet (e.g. right
+/// after startup, before the first monitor sweep has run).
+fn pick_next_healthy_validator(state: &AppState, exclude: &str) -> Option<String> {
+    let best = state
+        .validator_health
+        .iter()
+        .filter(|entry| entry.key() != exclude && entry.healthy)
+        .min_by_key(|entry| entry.latency_ms.unwrap_or(u64::MAX))
+        .map(|entry| entry.key().clone());
+
+    best.or_else(|| {
+        VALIDATORS
+            .iter()
+            .find(|v| **v != exclude && !state.validator_health.contains_key(**v))
+            .map(|v| v.to_string())
+    })
+}
+
+/// Periodically probes every configured validator's health and latency, and
+/// fails the active validator over to the healthiest responsive node when the
+/// current one stops answering.
+async fn spawn_validator_monitor(state: AppState) {
+    let mut interval = tokio::time::interval(Duration::from_secs(VALIDATOR_HEALTH_CHECK_INTERVAL_SECONDS));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+        refresh_validator_health(&state).await;
+    }
+}
+
+async fn refresh_validator_health(state: &AppState) {
+    let client = reqwest::Client::new();
+
+    for validator_url in VALIDATORS {
+        let health_req = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "system_health",
+            "params": [],
+            "id": 1
+        });
+
+        let started = Instant::now();
+        let healthy = matches!(
+            client.post(*validator_url).json(&health_req).timeout(Duration::from_secs(5)).send().await,
+            Ok(resp) if resp.status().is_success()
+        );
+        let latency_ms = healthy.then(|| started.elapsed().as_millis() as u64);
+
+        state
+            .metrics
+            .validator_health_checks
+            .with_label_values(&[*validator_url, if healthy { "success" } else { "failure" }])
+            .inc();
+        if let Some(latency_ms) = latency_ms {
+            state.metrics.validator_latency_ms.with_label_values(&[*validator_url]).set(latency_ms as i64);
+        }
+
+        state.validator_health.insert(
+            validator_url.to_string(),
+            ValidatorHealth { healthy, latency_ms, last_checked: Utc::now() },
+        );
+    }
+
+    let current = state.active_validator.read().await.clone();
+    let current_healthy = state.validator_health.get(&current).map(|h| h.healthy).unwrap_or(false);
+
+    if !current_healthy {
+        match pick_next_healthy_validator(state, &current) {
+            Some(next) if next != current => {
+                warn!("Active validator {} is unhealthy, failing over to {}", current, next);
+                *state.active_validator.write().await = next;
+            }
+            Some(_) => {}
+            None => warn!("Validator health sweep found no healthy endpoints"),
+        }
+    }
+}
+
+async fn submit_transfer_once(state: &AppState, validator_url: &str, to: &str, amount: u128, nonce: u32) -> Result<String> {
     let client = reqwest::Client::new();
 
     // Get genesis hash
     let genesis_hash = get_genesis_hash(validator_url).await?;
 
-    // Get nonce for Alice
-    let nonce = get_nonce(validator_url, ALICE_ADDRESS).await?;
-
     info!(
         "Submitting via gateway_submit: to={}, amount={}, nonce={}, genesis={}",
         to, amount, nonce, &genesis_hash[..16]
@@ -347,20 +894,29 @@ async fn submit_transfer(_state: &AppState, validator_url: &str, to: &str, amoun
         "id": 1
     });
 
+    let submit_started = Instant::now();
     let submit_resp = client
         .post(validator_url)
         .json(&submit_req)
         .timeout(Duration::from_secs(60))  // SPHINCS+ signing takes time
         .send()
-        .await?;
+        .await;
+    state.metrics.submit_latency_seconds.observe(submit_started.elapsed().as_secs_f64());
+    let submit_resp = submit_resp?;
 
     let submit_json: serde_json::Value = submit_resp.json().await?;
 
     if let Some(error) = submit_json.get("error") {
-        return Err(anyhow!(
-            "RPC error: {}",
-            error["message"].as_str().unwrap_or("Unknown error")
-        ));
+        let message = error["message"].as_str().unwrap_or("Unknown error");
+
+        // A stale or future nonce means our cache has drifted from chain state;
+        // drop it so the next submission re-seeds from `get_nonce`.
+        if message.to_lowercase().contains("nonce") {
+            warn!("Nonce rejected ({}), invalidating cached nonce", message);
+            invalidate_nonce_cache(state).await;
+        }
+
+        return Err(anyhow!("RPC error: {}", message));
     }
 
     // gateway_submit returns {"hash": "0x...", "status": "..."}
@@ -375,6 +931,110 @@ async fn submit_transfer(_state: &AppState, validator_url: &str, to: &str, amoun
     Ok(tx_hash)
 }
 
+/// Query the gateway for the on-chain status of a submitted tx hash.
+async fn check_tx_status(validator_url: &str, tx_hash: &str) -> Result<TxStatus> {
+    let client = reqwest::Client::new();
+
+    let req = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "gateway_txStatus",
+        "params": [tx_hash],
+        "id": 1
+    });
+
+    let resp = client
+        .post(validator_url)
+        .json(&req)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+
+    let json: serde_json::Value = resp.json().await?;
+
+    if let Some(error) = json.get("error") {
+        return Err(anyhow!("RPC error: {}", error["message"].as_str().unwrap_or("Unknown")));
+    }
+
+    match json["result"].as_str() {
+        Some("included") | Some("finalized") => Ok(TxStatus::Confirmed),
+        _ => Ok(TxStatus::Submitted),
+    }
+}
+
+/// Periodically walks `pending_txs`, dropping entries that are confirmed on-chain
+/// or have outlived `PENDING_TX_TTL_SECONDS`, so `/status` and `/pending` reflect
+/// reality instead of growing forever.
+async fn spawn_pending_tx_reaper(state: AppState) {
+    let mut interval = tokio::time::interval(Duration::from_secs(PENDING_TX_SWEEP_INTERVAL_SECONDS));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+        reap_pending_txs(&state).await;
+    }
+}
+
+async fn reap_pending_txs(state: &AppState) {
+    let validator = state.active_validator.read().await.clone();
+    let now = Utc::now();
+
+    // Snapshot the current entries and release the lock before making any
+    // network calls, so `/drip`, `/status`, and `/pending` are never blocked
+    // behind a sweep of (up to `Config::max_pending_txs`) sequential RPC round-trips.
+    let generation = state.pending_generation.load(Ordering::SeqCst);
+    let snapshot: Vec<PendingTx> = state.pending_txs.read().await.clone();
+    let before = snapshot.len();
+    let mut retained = Vec::with_capacity(snapshot.len());
+
+    for mut tx in snapshot {
+        if tx.status == TxStatus::Submitted {
+            match check_tx_status(&validator, &tx.tx_hash).await {
+                Ok(TxStatus::Confirmed) => {
+                    info!("Pending tx to {} confirmed (tx: {})", tx.to, tx.tx_hash);
+                    continue;
+                }
+                Ok(status) => tx.status = status,
+                Err(e) => warn!("Failed to check status for tx {}: {}", tx.tx_hash, e),
+            }
+        }
+
+        if now.signed_duration_since(tx.timestamp).num_seconds() > PENDING_TX_TTL_SECONDS {
+            tx.status = TxStatus::Expired;
+            warn!("Pending tx to {} expired after {}s (tx: {})", tx.to, PENDING_TX_TTL_SECONDS, tx.tx_hash);
+            continue;
+        }
+
+        retained.push(tx);
+    }
+
+    let removed = before - retained.len();
+    if removed > 0 {
+        info!("Pending-tx reaper removed {removed} entr{}", if removed == 1 { "y" } else { "ies" });
+    }
+
+    // Entries added to `pending_txs` while the sweep was running (e.g. by a
+    // concurrent `/drip`) aren't in `retained`; merge them back in rather than
+    // clobbering them with the stale snapshot.
+    state.metrics.pending_count.set(retained.len() as i64);
+    let mut pending = state.pending_txs.write().await;
+
+    // A generation bump means something (currently only `/admin/pending/flush`)
+    // replaced `pending_txs` wholesale while we were sweeping a now-stale view
+    // of it. Our `retained`/`before` bookkeeping no longer means anything
+    // relative to the current contents, so leave them untouched instead of
+    // clobbering or attempting to merge against them.
+    if state.pending_generation.load(Ordering::SeqCst) != generation {
+        state.metrics.pending_count.set(pending.len() as i64);
+        return;
+    }
+
+    if pending.len() > before {
+        retained.extend(pending.drain(before..));
+        state.metrics.pending_count.set(retained.len() as i64);
+    }
+    *pending = retained;
+}
+
 async fn find_active_validator() -> String {
     let client = reqwest::Client::new();
 
@@ -437,11 +1097,40 @@ fn index_html() -> &'static str {
         <div class="info">
             <p><strong>Rate limit:</strong> 1 request per minute per address</p>
             <p><strong>Amount:</strong> 10 QHT per request</p>
+            <p><strong>Anti-abuse:</strong> solves a small proof-of-work challenge before each drip</p>
             <p><a href="https://github.com/Paraxiom/quantumharmony" target="_blank">GitHub</a> | <a href="https://www.youtube.com/@Paraxiom" target="_blank">YouTube</a></p>
         </div>
     </div>
 
     <script>
+        function leadingZeroBits(bytes) {
+            let bits = 0;
+            for (const byte of bytes) {
+                if (byte === 0) { bits += 8; continue; }
+                for (let i = 7; i >= 0; i--) {
+                    if ((byte >> i) & 1) return bits;
+                    bits++;
+                }
+                break;
+            }
+            return bits;
+        }
+
+        async function sha256Bytes(message) {
+            const digest = await crypto.subtle.digest('SHA-256', new TextEncoder().encode(message));
+            return new Uint8Array(digest);
+        }
+
+        async function solveChallenge(token, difficultyBits) {
+            for (let nonce = 0; ; nonce++) {
+                const candidate = String(nonce);
+                const digest = await sha256Bytes(token + candidate);
+                if (leadingZeroBits(digest) >= difficultyBits) {
+                    return candidate;
+                }
+            }
+        }
+
         async function requestTokens() {
             const address = document.getElementById('address').value.trim();
             const btn = document.getElementById('btn');
@@ -453,14 +1142,23 @@ fn index_html() -> &'static str {
             }
 
             btn.disabled = true;
-            btn.textContent = 'Sending...';
             result.innerHTML = '';
 
             try {
+                btn.textContent = 'Solving proof-of-work...';
+                const challengeResp = await fetch('/challenge');
+                const challengeData = await challengeResp.json();
+                const nonce = await solveChallenge(challengeData.token, challengeData.difficulty_bits);
+
+                btn.textContent = 'Sending...';
                 const response = await fetch('/drip', {
                     method: 'POST',
                     headers: { 'Content-Type': 'application/json' },
-                    body: JSON.stringify({ address })
+                    body: JSON.stringify({
+                        address,
+                        challenge_token: challengeData.token,
+                        challenge_nonce: nonce
+                    })
                 });
 
                 const data = await response.json();
@@ -507,19 +1205,74 @@ async fn main() -> Result<()> {
     let active_validator = find_active_validator().await;
     info!("Using validator: {}", active_validator);
 
+    // Select the persistence backend. Defaults to in-memory; set
+    // `FAUCET_STORE=sqlite` (with the `sqlite` cargo feature enabled) for a
+    // store that survives restarts.
+    let store: Arc<dyn FaucetStore> = match std::env::var("FAUCET_STORE").as_deref() {
+        #[cfg(feature = "sqlite")]
+        Ok("sqlite") => {
+            let path = std::env::var("FAUCET_DB_PATH").unwrap_or_else(|_| "faucet.db".to_string());
+            info!("Using SQLite store at {}", path);
+            Arc::new(store::sqlite_store::SqliteStore::open(&path)?)
+        }
+        #[cfg(not(feature = "sqlite"))]
+        Ok("sqlite") => {
+            warn!("FAUCET_STORE=sqlite requested but built without the `sqlite` feature; falling back to in-memory");
+            Arc::new(InMemoryStore::new())
+        }
+        _ => Arc::new(InMemoryStore::new()),
+    };
+
+    let admin_token: Option<Arc<str>> = std::env::var("ADMIN_TOKEN").ok().map(Arc::from);
+    if admin_token.is_none() {
+        warn!("ADMIN_TOKEN not set; the /admin/* API is disabled");
+    }
+
     // Create app state
     let state = AppState {
-        rate_limits: Arc::new(DashMap::new()),
+        store,
         pending_txs: Arc::new(RwLock::new(Vec::new())),
+        pending_generation: Arc::new(AtomicU64::new(0)),
         active_validator: Arc::new(RwLock::new(active_validator)),
+        alice_nonce: Arc::new(Mutex::new(None)),
+        validator_health: Arc::new(DashMap::new()),
+        config: Arc::new(RwLock::new(Config::default())),
+        banned_addresses: Arc::new(DashSet::new()),
+        admin_token,
+        metrics: Arc::new(Metrics::new()),
+        challenges: Arc::new(DashMap::new()),
     };
 
+    // Start the background reaper that confirms or expires pending txs
+    tokio::spawn(spawn_pending_tx_reaper(state.clone()));
+
+    // Start the background validator health monitor and failover
+    tokio::spawn(spawn_validator_monitor(state.clone()));
+
+    // Start the background sweeper for unredeemed proof-of-work challenges
+    tokio::spawn(spawn_challenge_sweeper(state.clone()));
+
+    // Admin routes are gated by a bearer token so operators can retune the
+    // faucet at runtime without a recompile-and-redeploy cycle.
+    let admin_routes = Router::new()
+        .route("/admin/config", get(admin_get_config).post(admin_update_config))
+        .route("/admin/ban", post(admin_ban))
+        .route("/admin/unban", post(admin_unban))
+        .route("/admin/validator/refresh", post(admin_refresh_validator))
+        .route("/admin/pending/flush", post(admin_flush_pending))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_admin_token));
+
     // Build router
     let app = Router::new()
         .route("/", get(index))
         .route("/health", get(health_check))
         .route("/status", get(status))
+        .route("/metrics", get(metrics_endpoint))
+        .route("/challenge", get(challenge))
+        .route("/pending", get(pending))
+        .route("/history", get(history))
         .route("/drip", post(drip))
+        .merge(admin_routes)
         .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any).allow_headers(Any))
         .with_state(state);
 
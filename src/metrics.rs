@@ -0,0 +1,112 @@
+//! Prometheus instrumentation for the faucet.
+//!
+//! A single [`Metrics`] instance is threaded through `AppState` and scraped
+//! via `/metrics` in the standard Prometheus text exposition format.
+
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+
+pub struct Metrics {
+    registry: Registry,
+    pub drips_attempted: IntCounter,
+    pub drips_succeeded: IntCounter,
+    pub drips_failed: IntCounter,
+    pub drips_rejected: IntCounterVec,
+    pub pending_count: IntGauge,
+    pub block_height: IntGauge,
+    pub validator_health_checks: IntCounterVec,
+    pub validator_latency_ms: IntGaugeVec,
+    pub submit_latency_seconds: Histogram,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let drips_attempted = IntCounter::new(
+            "faucet_drips_attempted_total",
+            "Drip requests that passed validation and were submitted on-chain",
+        )
+        .expect("metric");
+        let drips_succeeded = IntCounter::new(
+            "faucet_drips_succeeded_total",
+            "Drip requests that were submitted successfully",
+        )
+        .expect("metric");
+        let drips_failed = IntCounter::new(
+            "faucet_drips_failed_total",
+            "Drip requests whose on-chain submission failed",
+        )
+        .expect("metric");
+        let drips_rejected = IntCounterVec::new(
+            Opts::new("faucet_drips_rejected_total", "Drip requests rejected before submission, by reason"),
+            &["reason"],
+        )
+        .expect("metric");
+        let pending_count =
+            IntGauge::new("faucet_pending_txs", "Number of transactions currently awaiting confirmation").expect("metric");
+        let block_height =
+            IntGauge::new("faucet_block_height", "Most recently observed chain block height").expect("metric");
+        let validator_health_checks = IntCounterVec::new(
+            Opts::new("faucet_validator_health_checks_total", "Validator health probes, by endpoint and outcome"),
+            &["validator", "outcome"],
+        )
+        .expect("metric");
+        let validator_latency_ms = IntGaugeVec::new(
+            Opts::new("faucet_validator_latency_ms", "Latency of the last successful health probe, by endpoint"),
+            &["validator"],
+        )
+        .expect("metric");
+        // gateway_submit is given a 60s timeout specifically because SPHINCS+
+        // signing is slow; the default client buckets top out at 10s, which
+        // would dump the whole distribution we care about into +Inf.
+        let submit_latency_seconds = Histogram::with_opts(
+            HistogramOpts::new(
+                "faucet_submit_transfer_latency_seconds",
+                "Latency of gateway_submit calls (SPHINCS+ signing dominates this)",
+            )
+            .buckets(vec![0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 15.0, 20.0, 30.0, 45.0, 60.0]),
+        )
+        .expect("metric");
+
+        registry.register(Box::new(drips_attempted.clone())).expect("register");
+        registry.register(Box::new(drips_succeeded.clone())).expect("register");
+        registry.register(Box::new(drips_failed.clone())).expect("register");
+        registry.register(Box::new(drips_rejected.clone())).expect("register");
+        registry.register(Box::new(pending_count.clone())).expect("register");
+        registry.register(Box::new(block_height.clone())).expect("register");
+        registry.register(Box::new(validator_health_checks.clone())).expect("register");
+        registry.register(Box::new(validator_latency_ms.clone())).expect("register");
+        registry.register(Box::new(submit_latency_seconds.clone())).expect("register");
+
+        Metrics {
+            registry,
+            drips_attempted,
+            drips_succeeded,
+            drips_failed,
+            drips_rejected,
+            pending_count,
+            block_height,
+            validator_health_checks,
+            validator_latency_ms,
+            submit_latency_seconds,
+        }
+    }
+
+    /// Render all registered metrics in Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let encoder = TextEncoder::new();
+        let metric_families = self.registry.gather();
+        let mut buffer = Vec::new();
+        encoder.encode(&metric_families, &mut buffer).expect("encode metrics");
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
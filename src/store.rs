@@ -0,0 +1,175 @@
+//! Pluggable persistence for rate-limit state and drip history.
+//!
+//! The faucet restarts often during testnet operations; with only an
+//! in-memory map, every restart wipes the rate-limit window and loses the
+//! record of what was sent. `FaucetStore` abstracts over the backing store
+//! so the in-memory default and the SQLite-backed option (enabled via the
+//! `sqlite` feature and `FAUCET_STORE=sqlite`) share the same call sites.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DripRecord {
+    pub address: String,
+    pub amount: u128,
+    pub tx_hash: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait FaucetStore: Send + Sync {
+    /// Record that `address` was just dripped, updating the rate-limit window.
+    async fn record_drip(&self, address: &str, timestamp: DateTime<Utc>);
+
+    /// Last time `address` successfully received a drip, if any.
+    async fn last_drip_for(&self, address: &str) -> Option<DateTime<Utc>>;
+
+    /// Append a completed drip to the durable history log.
+    async fn append_history(&self, record: DripRecord);
+
+    /// History entries for `address`, newest first.
+    async fn history_for(&self, address: &str) -> Vec<DripRecord>;
+}
+
+/// Default backend: state lives only for the lifetime of the process.
+#[derive(Default)]
+pub struct InMemoryStore {
+    rate_limits: RwLock<HashMap<String, DateTime<Utc>>>,
+    history: RwLock<Vec<DripRecord>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl FaucetStore for InMemoryStore {
+    async fn record_drip(&self, address: &str, timestamp: DateTime<Utc>) {
+        self.rate_limits.write().await.insert(address.to_string(), timestamp);
+    }
+
+    async fn last_drip_for(&self, address: &str) -> Option<DateTime<Utc>> {
+        self.rate_limits.read().await.get(address).copied()
+    }
+
+    async fn append_history(&self, record: DripRecord) {
+        self.history.write().await.push(record);
+    }
+
+    async fn history_for(&self, address: &str) -> Vec<DripRecord> {
+        self.history
+            .read()
+            .await
+            .iter()
+            .rev()
+            .filter(|r| r.address == address)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite_store {
+    use super::*;
+    use rusqlite::{params, Connection};
+    use std::sync::Mutex;
+
+    /// Durable backend: rate limits and history survive a restart because
+    /// `last_drip_for`/`history_for` read straight from the on-disk table.
+    pub struct SqliteStore {
+        conn: Mutex<Connection>,
+    }
+
+    impl SqliteStore {
+        pub fn open(path: &str) -> rusqlite::Result<Self> {
+            let conn = Connection::open(path)?;
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS rate_limits (
+                    address TEXT PRIMARY KEY,
+                    last_drip TEXT NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS history (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    address TEXT NOT NULL,
+                    amount TEXT NOT NULL,
+                    tx_hash TEXT NOT NULL,
+                    timestamp TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_history_address ON history(address);",
+            )?;
+            Ok(Self { conn: Mutex::new(conn) })
+        }
+    }
+
+    #[async_trait]
+    impl FaucetStore for SqliteStore {
+        async fn record_drip(&self, address: &str, timestamp: DateTime<Utc>) {
+            let conn = self.conn.lock().unwrap();
+            let _ = conn.execute(
+                "INSERT INTO rate_limits (address, last_drip) VALUES (?1, ?2)
+                 ON CONFLICT(address) DO UPDATE SET last_drip = excluded.last_drip",
+                params![address, timestamp.to_rfc3339()],
+            );
+        }
+
+        async fn last_drip_for(&self, address: &str) -> Option<DateTime<Utc>> {
+            let conn = self.conn.lock().unwrap();
+            conn.query_row(
+                "SELECT last_drip FROM rate_limits WHERE address = ?1",
+                params![address],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|ts| ts.with_timezone(&Utc))
+        }
+
+        async fn append_history(&self, record: DripRecord) {
+            let conn = self.conn.lock().unwrap();
+            let _ = conn.execute(
+                "INSERT INTO history (address, amount, tx_hash, timestamp) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    record.address,
+                    record.amount.to_string(),
+                    record.tx_hash,
+                    record.timestamp.to_rfc3339()
+                ],
+            );
+        }
+
+        async fn history_for(&self, address: &str) -> Vec<DripRecord> {
+            let conn = self.conn.lock().unwrap();
+            let mut stmt = match conn.prepare(
+                "SELECT address, amount, tx_hash, timestamp FROM history
+                 WHERE address = ?1 ORDER BY id DESC",
+            ) {
+                Ok(stmt) => stmt,
+                Err(_) => return Vec::new(),
+            };
+
+            let rows = stmt.query_map(params![address], |row| {
+                let amount: String = row.get(1)?;
+                let timestamp: String = row.get(3)?;
+                Ok(DripRecord {
+                    address: row.get(0)?,
+                    amount: amount.parse().unwrap_or(0),
+                    tx_hash: row.get(2)?,
+                    timestamp: DateTime::parse_from_rfc3339(&timestamp)
+                        .map(|ts| ts.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                })
+            });
+
+            match rows {
+                Ok(rows) => rows.filter_map(Result::ok).collect(),
+                Err(_) => Vec::new(),
+            }
+        }
+    }
+}